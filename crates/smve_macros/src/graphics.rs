@@ -1,26 +1,142 @@
-use proc_macro::{TokenStream};
+use proc_macro::TokenStream;
 use quote::quote;
-use syn::{DeriveInput, parse_macro_input, parse_quote};
+use syn::meta::ParseNestedMeta;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, LitStr};
+
+/// Everything that can be configured with `#[extract_component(...)]` and
+/// `#[extract_component_filter(...)]`
+#[derive(Default)]
+struct ExtractComponentAttributes {
+    /// The query filter, `()` if `None`
+    filter: Option<syn::Type>,
+    /// Whether the component is taken out of the main world instead of cloned
+    take: bool,
+    /// The type extracted into the render world, `Self` if `None`
+    into: Option<syn::Type>,
+    /// The conversion function, `From` is used if `None`
+    with: Option<syn::Path>,
+}
+
+impl ExtractComponentAttributes {
+    /// Parses all the relevant attributes on the derive input, combining every error found
+    fn parse(ast: &DeriveInput) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+        let mut mode: Option<LitStr> = None;
+        let mut errors: Option<syn::Error> = None;
+
+        for attribute in &ast.attrs {
+            let result = if attribute.path().is_ident("extract_component_filter") {
+                // The argument to the attribute should be a type, otherwise compile error
+                attribute.parse_args::<syn::Type>().and_then(|filter| {
+                    if attributes.filter.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            attribute,
+                            "duplicate extract_component filter",
+                        ));
+                    }
+                    attributes.filter = Some(filter);
+                    Ok(())
+                })
+            } else if attribute.path().is_ident("extract_component") {
+                attribute.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("filter") {
+                        set_once(&meta, &mut attributes.filter)
+                    } else if meta.path.is_ident("mode") {
+                        set_once(&meta, &mut mode)
+                    } else if meta.path.is_ident("into") {
+                        set_once(&meta, &mut attributes.into)
+                    } else if meta.path.is_ident("with") {
+                        set_once(&meta, &mut attributes.with)
+                    } else {
+                        Err(meta.error(
+                            "unsupported extract_component property, expected one of `filter`, `mode`, `into` or `with`",
+                        ))
+                    }
+                })
+            } else {
+                Ok(())
+            };
+
+            if let Err(e) = result {
+                combine(&mut errors, e);
+            }
+        }
+
+        if let Some(mode) = mode {
+            match mode.value().as_str() {
+                "clone" => attributes.take = false,
+                "take" => attributes.take = true,
+                _ => combine(
+                    &mut errors,
+                    syn::Error::new(mode.span(), "expected `\"clone\"` or `\"take\"`"),
+                ),
+            }
+        }
+
+        match errors {
+            Some(errors) => Err(errors),
+            None => Ok(attributes),
+        }
+    }
+}
+
+/// Parses the value of a `key = value` property, erroring if it was already set
+fn set_once<T: syn::parse::Parse>(meta: &ParseNestedMeta, slot: &mut Option<T>) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(meta.error("duplicate extract_component property"));
+    }
+    *slot = Some(meta.value()?.parse()?);
+    Ok(())
+}
+
+/// Adds `error` to the errors collected so far
+fn combine(errors: &mut Option<syn::Error>, error: syn::Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
+    }
+}
 
 pub fn derive_extract_component(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
-    
-    // Require `Clone` for the component
-    ast.generics.make_where_clause().predicates.push(parse_quote! { Self: Clone });
-    
+
+    if let Data::Union(data) = &ast.data {
+        return syn::Error::new(
+            data.union_token.span,
+            "ExtractComponent can only be derived for structs and enums",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let ExtractComponentAttributes {
+        filter,
+        take,
+        into,
+        with,
+    } = match ExtractComponentAttributes::parse(&ast) {
+        Ok(attributes) => attributes,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    // Cloning requires `Clone` unless the component is converted, taking leaves a `Default` value
+    // behind in the main world
+    if take {
+        ast.generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { Self: Default });
+    } else if into.is_none() && with.is_none() {
+        ast.generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { Self: Clone });
+    }
+
     let struct_name = &ast.ident;
-    
-    let filter = if let Some(attribute) = ast
-        .attrs
-        .iter()
-        .find(|a| a.path().is_ident("extract_component_filter"))
-    {
-        // The argument to the attribute should be a type, otherwise compile error
-        let filter = match attribute.parse_args::<syn::Type>() {
-            Ok(filter) => filter,
-            Err(e) => return e.to_compile_error().into(),
-        };
-        
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+
+    let filter = if let Some(filter) = filter {
         quote! {
             #filter
         }
@@ -30,15 +146,54 @@ pub fn derive_extract_component(input: TokenStream) -> TokenStream {
         }
     };
 
+    let out = if let Some(into) = &into {
+        quote! {
+            #into
+        }
+    } else {
+        quote! {
+            Self
+        }
+    };
+
+    // Converts `item` (`&Self` when cloning, `Self` when taking) into the output type
+    let convert = match (&with, &into, take) {
+        (Some(with), _, _) => quote! { #with(item) },
+        (None, Some(into), false) => quote! { <#into as From<&Self>>::from(item) },
+        (None, Some(into), true) => quote! { <#into as From<Self>>::from(item) },
+        (None, None, false) => quote! { item.clone() },
+        (None, None, true) => quote! { item },
+    };
+
+    let extract = if take {
+        quote! {
+            const MODE: crate::client::core::graphics::extract::utils::extract_component::ExtractMode =
+                crate::client::core::graphics::extract::utils::extract_component::ExtractMode::Take;
+
+            fn extract_component(_item: bevy_ecs::query::QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+                None
+            }
+
+            fn take_component(component: &mut Self) -> Option<Self::Out> {
+                let item = std::mem::take(component);
+                Some(#convert)
+            }
+        }
+    } else {
+        quote! {
+            fn extract_component(item: bevy_ecs::query::QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+                Some(#convert)
+            }
+        }
+    };
+
     TokenStream::from(quote! {
-        impl crate::client::core::graphics::extract::utils::extract_component::ExtractComponent for #struct_name {
+        impl #impl_generics crate::client::core::graphics::extract::utils::extract_component::ExtractComponent for #struct_name #type_generics #where_clause {
             type QueryData = &'static Self;
             type QueryFilter = #filter;
-            type Out = Self;
+            type Out = #out;
 
-            fn extract_component(item: bevy_ecs::query::QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
-                Some(item.clone())
-            }
+            #extract
         }
     })
-}
\ No newline at end of file
+}
@@ -3,9 +3,24 @@ mod graphics;
 use proc_macro::TokenStream;
 
 /// Implements the `ExtractComponent` trait for a component.
-/// The component must implement [`Clone`]
-/// The component will be extracted into the render world as is (`.clone()`)
-/// 
+/// By default the component must implement [`Clone`]
+/// and will be extracted into the render world as is (`.clone()`)
+///
+/// # Attributes
+/// - `#[extract_component(filter = ...)]` or `#[extract_component_filter(...)]`: Only extract
+///   components matching the query filter
+/// - `#[extract_component(mode = "take")]`: Move the component into the render world with
+///   [`std::mem::take`] instead of cloning it. The component must implement [`Default`] and is
+///   left as its default value in the main world. Only components changed since the last
+///   extraction are taken, so the default value is never extracted. The render world is cleared
+///   every frame, so the renderer only sees the value once: only use this for large data which
+///   the main world rebuilds for the renderer every frame, never for persistent data.
+/// - `#[extract_component(into = RenderFoo)]`: Extract the component as `RenderFoo` instead,
+///   converted with `From<&Self>` (or `From<Self>` when taking). The component does not need to
+///   implement [`Clone`].
+/// - `#[extract_component(with = convert_fn)]`: Convert the component with `convert_fn` instead,
+///   which takes `&Self` (or `Self` when taking) and returns the `into` type (or `Self`).
+///
 /// # Example
 /// ```no_compile
 /// // This will extract any Foo with a Camera into the render world via Clone
@@ -14,14 +29,35 @@ use proc_macro::TokenStream;
 /// pub struct Foo {
 ///     // Snip --
 /// }
-/// 
+///
 /// // This will extract all Bar into the render world via Clone
 /// #[derive(Component, Clone, ExtractComponent)]
 /// pub struct Bar {
 ///     // Snip --
 /// }
+///
+/// // This will extract all Qux into the render world as RenderQux via qux_to_render
+/// #[derive(Component, ExtractComponent)]
+/// #[extract_component(into = RenderQux, with = qux_to_render)]
+/// pub struct Qux {
+///     // Snip --
+/// }
+///
+/// fn qux_to_render(qux: &Qux) -> RenderQux {
+///     // Snip --
+/// }
+///
+/// // This will move all Baz into the render world, leaving Baz::default() behind
+/// #[derive(Component, Default, ExtractComponent)]
+/// #[extract_component(mode = "take")]
+/// pub struct Baz {
+///     // Snip --
+/// }
 /// ```
-#[proc_macro_derive(ExtractComponent)]
+#[proc_macro_derive(
+    ExtractComponent,
+    attributes(extract_component, extract_component_filter)
+)]
 pub fn derive_extract_component(item: TokenStream) -> TokenStream {
     graphics::derive_extract_component(item)
-}
\ No newline at end of file
+}
@@ -2,19 +2,17 @@
 
 use bevy_ecs::entity::Entity;
 use bevy_ecs::prelude::Component;
+use glam::{Mat4, Quat, Vec3};
 use smve_macros::ExtractComponent;
 use wgpu::Color;
 
 /// A component representing a camera and its settings.
 ///
 /// Not exhaustive at the moment, but it will be expanded with more fields later on.
-#[derive(Component, Clone, ExtractComponent, Default)]
+#[derive(Component, Clone, ExtractComponent)]
 pub struct Camera {
     /// Where the camera renders to
     ///
-    /// Only supports rendering to windows for now, but will eventually support rendering to
-    /// textures.
-    ///
     /// # See Also
     /// [`CameraRenderTarget`]
     pub render_target: CameraRenderTarget,
@@ -23,11 +21,30 @@ pub struct Camera {
     /// # See Also
     /// [`CameraClearBehaviour`]
     pub clear_behaviour: CameraClearBehaviour,
+    /// How the camera projects the world onto the render target
+    ///
+    /// # See Also
+    /// [`Projection`]
+    pub projection: Projection,
+    /// The width of the render target divided by its height
+    ///
+    /// This is updated automatically for cameras rendering to a window and has to be set manually
+    /// for cameras rendering to a texture.
+    pub aspect_ratio: f32,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera {
+            render_target: Default::default(),
+            clear_behaviour: Default::default(),
+            projection: Default::default(),
+            aspect_ratio: 1.0,
+        }
+    }
 }
 
 /// Where a camera renders to.
-///
-/// Will eventually support rendering to textures.
 #[non_exhaustive]
 #[allow(dead_code)]
 #[derive(Clone, Default)]
@@ -37,6 +54,10 @@ pub enum CameraRenderTarget {
     PrimaryWindow,
     /// Rendering to a window
     Window(Entity),
+    /// Rendering to the offscreen render target with this name
+    ///
+    /// The render target is created with [`GraphicsState::create_render_target`](crate::client::core::graphics::resources::GraphicsState::create_render_target).
+    Texture(String),
     /// Ignores the camera when rendering
     None,
 }
@@ -51,23 +72,154 @@ impl CameraRenderTarget {
         match self {
             CameraRenderTarget::PrimaryWindow => primary_window,
             CameraRenderTarget::Window(entity) => Some(*entity),
-            CameraRenderTarget::None => None,
+            CameraRenderTarget::Texture(_) | CameraRenderTarget::None => None,
         }
     }
 }
 
 /// How a camera clears the render target.
-#[derive(Clone)]
+#[derive(Clone, Default)]
 #[allow(dead_code)]
 pub enum CameraClearBehaviour {
+    /// Clears the target with the color in the [`ClearColor`](super::resources::ClearColor) resource
+    #[default]
+    Default,
     /// Do not clear the target at the start of the frame
     DontClear,
     /// Clears the target with the supplied color
     Color(Color),
 }
 
-impl Default for CameraClearBehaviour {
+/// How a camera projects the world onto its render target.
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Projection {
+    /// Objects further away appear smaller
+    Perspective {
+        /// The vertical field of view in radians
+        fov: f32,
+        /// The distance to the near clipping plane
+        near: f32,
+        /// The distance to the far clipping plane
+        far: f32,
+    },
+    /// Objects appear the same size regardless of distance, for UI, top-down and isometric views
+    Orthographic {
+        /// The height of the view in world units, the width follows from the aspect ratio
+        scale: f32,
+        /// The distance to the near clipping plane
+        near: f32,
+        /// The distance to the far clipping plane
+        far: f32,
+    },
+}
+
+impl Default for Projection {
+    fn default() -> Self {
+        Projection::Perspective {
+            fov: std::f32::consts::FRAC_PI_4,
+            near: 0.1,
+            far: 1000.0,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Projection {
+    /// Creates an orthographic projection showing `scale` world units vertically
+    pub fn orthographic(scale: f32) -> Self {
+        Projection::Orthographic {
+            scale,
+            near: 0.0,
+            far: 1000.0,
+        }
+    }
+
+    /// Returns the projection matrix, mapping depth to the `0..1` range wgpu uses
+    ///
+    /// # Arguments
+    /// - `aspect_ratio` - The width of the render target divided by its height
+    pub fn get_projection_matrix(&self, aspect_ratio: f32) -> Mat4 {
+        match *self {
+            Projection::Perspective { fov, near, far } => {
+                Mat4::perspective_rh(fov, aspect_ratio, near, far)
+            }
+            Projection::Orthographic { scale, near, far } => {
+                let half_height = scale / 2.0;
+                let half_width = half_height * aspect_ratio;
+                Mat4::orthographic_rh(
+                    -half_width,
+                    half_width,
+                    -half_height,
+                    half_height,
+                    near,
+                    far,
+                )
+            }
+        }
+    }
+}
+
+/// The position and orientation of an entity.
+///
+/// Cameras look along their local negative Z axis, with positive Y being up.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    /// The position of the entity
+    pub translation: Vec3,
+    /// The rotation of the entity
+    pub rotation: Quat,
+}
+
+impl Default for Transform {
     fn default() -> Self {
-        CameraClearBehaviour::Color(Color::BLACK)
+        Transform::IDENTITY
+    }
+}
+
+#[allow(dead_code)]
+impl Transform {
+    /// A transform at the origin with no rotation
+    pub const IDENTITY: Transform = Transform {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+    };
+
+    /// Creates a transform at the given position with no rotation
+    pub fn from_translation(translation: Vec3) -> Self {
+        Transform {
+            translation,
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// Rotates the transform so that its negative Z axis points at `target`
+    ///
+    /// # Arguments
+    /// - `target` - The position to look at
+    /// - `up` - The direction considered up, must not be parallel to the direction to `target`
+    pub fn looking_at(mut self, target: Vec3, up: Vec3) -> Self {
+        let view = Mat4::look_at_rh(self.translation, target, up);
+        self.rotation = Quat::from_mat4(&view.inverse());
+        self
     }
+
+    /// Returns the matrix transforming from the local space of the entity to world space
+    pub fn compute_matrix(&self) -> Mat4 {
+        Mat4::from_rotation_translation(self.rotation, self.translation)
+    }
+}
+
+/// The matrices of a camera, computed from its [`Transform`] and projection every frame.
+///
+/// This is added to cameras automatically and extracted into the render world.
+#[allow(dead_code)]
+#[derive(Component, Clone, Copy, PartialEq, Debug, Default, ExtractComponent)]
+pub struct ViewProjection {
+    /// Transforms from world space to view space (the inverse of the camera's transform)
+    pub view: Mat4,
+    /// Transforms from view space to clip space
+    pub projection: Mat4,
+    /// `projection * view`, transforms from world space to clip space
+    pub view_projection: Mat4,
 }
@@ -1,12 +1,19 @@
 //! Contains camera related functionality including the [`CameraPlugin`]
 
 pub mod components;
+pub mod resources;
+mod systems;
 
-use bevy_app::{App, Plugin};
+use crate::client::core::graphics::camera::systems::pu_update_view_projections;
+use bevy_app::{App, Plugin, PostUpdate};
 
 /// Plugin containing functionality to do with a camera.
+///
+/// Computes the [`ViewProjection`](components::ViewProjection) of every camera, which is extracted into the render world.
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
-    fn build(&self, _app: &mut App) {}
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, pu_update_view_projections);
+    }
 }
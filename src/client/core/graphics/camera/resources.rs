@@ -0,0 +1,32 @@
+//! Contains the resources related to the camera
+
+use bevy_ecs::system::Resource;
+use std::ops::{Deref, DerefMut};
+use wgpu::Color;
+
+/// The color cameras clear their render target with when their clear behaviour is
+/// [`CameraClearBehaviour::Default`](super::components::CameraClearBehaviour::Default).
+///
+/// Change this at runtime to change the background of every such camera at once.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ClearColor(pub Color);
+
+impl Default for ClearColor {
+    fn default() -> Self {
+        ClearColor(Color::BLACK)
+    }
+}
+
+impl Deref for ClearColor {
+    type Target = Color;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ClearColor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -0,0 +1,60 @@
+//! Systems that keep the camera matrices up to date
+
+use crate::client::core::graphics::camera::components::{Camera, Transform, ViewProjection};
+use crate::client::core::window::components::{PrimaryWindow, Window};
+use bevy_ecs::prelude::{Commands, Entity, Query, With};
+
+/// Updates the aspect ratio of cameras rendering to windows and computes their [`ViewProjection`]
+///
+/// Cameras without a [`Transform`] are treated as being at the origin.
+/// Runs on `PostUpdate`
+pub fn pu_update_view_projections(
+    mut commands: Commands,
+    mut cameras: Query<(
+        Entity,
+        &mut Camera,
+        Option<&Transform>,
+        Option<&mut ViewProjection>,
+    )>,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+) {
+    let primary_window = primary_window.get_single().ok();
+
+    for (entity, mut camera, transform, view_projection) in cameras.iter_mut() {
+        // Match the aspect ratio of the window the camera renders to
+        if let Some(window) = camera
+            .render_target
+            .get_window_entity(primary_window)
+            .and_then(|window| windows.get(window).ok())
+        {
+            let aspect_ratio = window.resolution.physical_width().max(1) as f32
+                / window.resolution.physical_height().max(1) as f32;
+            // Only write when it changes so change detection on the camera stays meaningful
+            if camera.aspect_ratio != aspect_ratio {
+                camera.aspect_ratio = aspect_ratio;
+            }
+        }
+
+        let view = transform
+            .copied()
+            .unwrap_or_default()
+            .compute_matrix()
+            .inverse();
+        let projection = camera.projection.get_projection_matrix(camera.aspect_ratio);
+        let new_view_projection = ViewProjection {
+            view,
+            projection,
+            view_projection: projection * view,
+        };
+
+        if let Some(mut view_projection) = view_projection {
+            // Only write when it changes so change detection on the view projection stays meaningful
+            if *view_projection != new_view_projection {
+                *view_projection = new_view_projection;
+            }
+        } else {
+            commands.entity(entity).insert(new_view_projection);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Bevy events for the graphics module.
+
+use crate::client::core::graphics::resources::RenderError;
+use bevy_ecs::prelude::Event;
+
+/// This event is emitted on the main world when the render world reports an error.
+#[derive(Event)]
+#[allow(dead_code)]
+pub struct RenderErrorEvent {
+    /// The error that happened
+    pub error: RenderError,
+}
@@ -1,14 +1,35 @@
-//! Responsible for extracting the camera into the render world
+//! Responsible for extracting the camera, its matrices and its clear color into the render world
 
-use crate::client::core::graphics::camera::components::Camera;
+use crate::client::core::graphics::camera::components::{Camera, ViewProjection};
+use crate::client::core::graphics::camera::resources::ClearColor;
 use crate::client::core::graphics::extract::utils::extract_component::ExtractComponentPlugin;
+use crate::client::core::graphics::extract::utils::extract_param::Extract;
+use crate::client::core::graphics::{ExtractSchedule, RenderSubApp};
 use bevy_app::{App, Plugin};
+use bevy_ecs::change_detection::DetectChanges;
+use bevy_ecs::system::{Commands, Res};
 
-/// Extracts Cameras into the render world
+/// Extracts Cameras, their [`ViewProjection`] and the [`ClearColor`] into the render world
 pub struct CameraExtractPlugin;
 
 impl Plugin for CameraExtractPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(ExtractComponentPlugin::<Camera>::default());
+        app.add_plugins(ExtractComponentPlugin::<Camera>::default())
+            .add_plugins(ExtractComponentPlugin::<ViewProjection>::default());
+
+        app.init_resource::<ClearColor>();
+        if let Ok(render_app) = app.get_sub_app_mut(RenderSubApp) {
+            render_app.init_resource::<ClearColor>();
+            render_app.add_systems(ExtractSchedule, e_extract_clear_color);
+        }
+    }
+}
+
+/// Copies the [`ClearColor`] into the render world when it changes
+///
+/// Runs on `Extract`.
+fn e_extract_clear_color(mut commands: Commands, clear_color: Extract<Res<ClearColor>>) {
+    if clear_color.is_changed() {
+        commands.insert_resource(**clear_color);
     }
 }
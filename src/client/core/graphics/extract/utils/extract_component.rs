@@ -1,13 +1,16 @@
-//! Utility trait to easily extract components into the render world by cloning
+//! Utility trait to easily extract components into the render world by cloning or taking them
 
 use crate::client::core::graphics::extract::utils::extract_param::Extract;
+use crate::client::core::graphics::resources::MainWorld;
 use crate::client::core::graphics::{ExtractSchedule, RenderSubApp};
 use bevy_app::{App, Plugin};
 use bevy_ecs::bundle::Bundle;
+use bevy_ecs::change_detection::{DetectChanges, DetectChangesMut};
+use bevy_ecs::component::Tick;
 use bevy_ecs::entity::Entity;
 use bevy_ecs::prelude::{Component, Local};
-use bevy_ecs::query::{QueryFilter, QueryItem, ReadOnlyQueryData};
-use bevy_ecs::system::{Commands, Query};
+use bevy_ecs::query::{QueryFilter, QueryItem, QueryState, ReadOnlyQueryData};
+use bevy_ecs::system::{Commands, Query, ResMut};
 use std::marker::PhantomData;
 
 /// A trait representing the extraction from the main world to the render world
@@ -40,8 +43,43 @@ pub trait ExtractComponent: Component {
     /// This bundle will be added to the render world after the extraction
     type Out: Bundle;
 
+    /// Whether the component is cloned or taken out of the main world
+    ///
+    /// # See Also
+    /// [`ExtractMode`]
+    const MODE: ExtractMode = ExtractMode::Clone;
+
     /// Defines how the component is transferred to the render world
     fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out>;
+
+    /// Defines how the component is moved to the render world when [`MODE`](Self::MODE) is
+    /// [`ExtractMode::Take`]
+    ///
+    /// Anything left in `component` stays in the main world.
+    fn take_component(_component: &mut Self) -> Option<Self::Out> {
+        None
+    }
+}
+
+/// How a component is transferred to the render world.
+#[allow(dead_code)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExtractMode {
+    /// Reads the component through [`QueryData`](ExtractComponent::QueryData) and calls
+    /// [`extract_component`](ExtractComponent::extract_component)
+    Clone,
+    /// Mutably accesses the component in the main world and calls
+    /// [`take_component`](ExtractComponent::take_component)
+    ///
+    /// This avoids cloning large components every frame, but the main world loses whatever
+    /// was taken. Only components changed since the last extraction are taken, so the default
+    /// value left behind never reaches the renderer. The render world is cleared every frame, so
+    /// a taken value only reaches the renderer once: this is only meant for data the main world
+    /// rebuilds every frame.
+    ///
+    /// Taking needs mutable access to the main world, so it does not run in parallel with other
+    /// extract systems.
+    Take,
 }
 
 /// Add this plugin to the main app to extract the component
@@ -65,7 +103,14 @@ impl<C> Default for ExtractComponentPlugin<C> {
 impl<C: ExtractComponent> Plugin for ExtractComponentPlugin<C> {
     fn build(&self, app: &mut App) {
         if let Ok(render_app) = app.get_sub_app_mut(RenderSubApp) {
-            render_app.add_systems(ExtractSchedule, e_extract_components::<C>);
+            match C::MODE {
+                ExtractMode::Clone => {
+                    render_app.add_systems(ExtractSchedule, e_extract_components::<C>);
+                }
+                ExtractMode::Take => {
+                    render_app.add_systems(ExtractSchedule, e_take_components::<C>);
+                }
+            }
         }
     }
 }
@@ -90,3 +135,41 @@ fn e_extract_components<C: ExtractComponent>(
     *previous_len = values.len();
     commands.insert_or_spawn_batch(values);
 }
+
+/// A system that runs the [`take_component`][1] function for all the components changed since
+/// it last ran
+///
+/// Runs on `Extract`.
+///
+/// [1]: ExtractComponent::take_component()
+fn e_take_components<C: ExtractComponent>(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    mut query: Local<Option<QueryState<(Entity, &'static mut C), C::QueryFilter>>>,
+    mut last_run: Local<Option<Tick>>,
+    mut main_world: ResMut<MainWorld>,
+) {
+    let query = query.get_or_insert_with(|| main_world.query_filtered());
+    let this_run = main_world.change_tick();
+
+    let mut values = Vec::with_capacity(*previous_len);
+    for (entity, mut component) in query.iter_mut(&mut main_world) {
+        // Unchanged components only hold the default value left behind by the last take.
+        // Compared against the last run instead of using `Changed`, so changes made in frames
+        // where extraction was skipped are not missed.
+        if last_run
+            .is_some_and(|last_run| !component.last_changed().is_newer_than(last_run, this_run))
+        {
+            continue;
+        }
+
+        // Taking the value for the renderer is not a change the main world should react to
+        if let Some(component) = C::take_component(component.bypass_change_detection()) {
+            values.push((entity, component));
+        }
+    }
+
+    *last_run = Some(this_run);
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
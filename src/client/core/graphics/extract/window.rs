@@ -3,11 +3,11 @@
 use crate::client::core::graphics::extract::utils::extract_param::Extract;
 use crate::client::core::graphics::resources::{ExtractedWindows, GraphicsState};
 use crate::client::core::graphics::{ExtractSchedule, RenderSubApp};
-use crate::client::core::window::components::{PrimaryWindow, RawHandleWrapper, Window};
-use crate::client::core::window::events::CloseRequestedEvent;
+use crate::client::core::window::components::{
+    PresentMode, PrimaryWindow, RawHandleWrapper, SurfaceFormat, Window,
+};
 use bevy_app::{App, Plugin};
 use bevy_ecs::entity::Entity;
-use bevy_ecs::event::EventReader;
 use bevy_ecs::system::{Query, ResMut};
 
 /// Responsible for extracting the windows into the render world
@@ -29,21 +29,22 @@ pub struct ExtractedWindow {
     pub physical_width: u32,
     /// Physical height in pixels of the window
     pub physical_height: u32,
-    /// Whether V-Sync is enabled for the window
-    pub vsync: bool,
+    /// The requested present mode of the window
+    pub present_mode: PresentMode,
+    /// The requested surface format of the window
+    pub surface_format: SurfaceFormat,
     /// Raw handles of the window
     pub raw_handles: RawHandleWrapper,
     /// Whether the window size has changed since last frame
     pub size_changed: bool,
-    /// Whether the vsync value was changed since last frame
-    pub present_mode_changed: bool,
+    /// Whether the surface format was changed since last frame
+    pub surface_format_changed: bool,
 }
 
 /// System added to the extract schedule to extract windows
 fn e_extract_windows(
     mut extracted_windows: ResMut<ExtractedWindows>,
     main_world_query: Extract<Query<(Entity, &Window, &RawHandleWrapper, Option<&PrimaryWindow>)>>,
-    mut closed_windows: Extract<EventReader<CloseRequestedEvent>>,
     mut graphics_state: ResMut<GraphicsState<'static>>,
 ) {
     for (entity, window, handle, primary) in main_world_query.iter() {
@@ -60,29 +61,44 @@ fn e_extract_windows(
         let extracted_window = extracted_windows.entry(entity).or_insert(ExtractedWindow {
             physical_width: new_width,
             physical_height: new_height,
-            vsync: window.vsync,
+            present_mode: window.present_mode,
+            surface_format: window.surface_format,
             raw_handles: handle.clone(),
             size_changed: false,
-            present_mode_changed: false,
+            surface_format_changed: false,
         });
 
         // This relies on the fact that `extracted_window` will reflect the old values if it already exists
         extracted_window.size_changed = new_width != extracted_window.physical_width
             || new_height != extracted_window.physical_height;
-        extracted_window.present_mode_changed = window.vsync != extracted_window.vsync;
+        extracted_window.surface_format_changed =
+            window.surface_format != extracted_window.surface_format;
 
         if extracted_window.size_changed {
             extracted_window.physical_width = new_width;
             extracted_window.physical_height = new_height;
         }
 
-        if extracted_window.present_mode_changed {
-            extracted_window.vsync = window.vsync;
+        // The render world compares this against the surface, so it is always kept up to date
+        extracted_window.present_mode = window.present_mode;
+
+        if extracted_window.surface_format_changed {
+            extracted_window.surface_format = window.surface_format;
         }
     }
 
-    for closed_window in closed_windows.read() {
-        extracted_windows.remove(&closed_window.entity);
-        graphics_state.destroy_surface(closed_window.entity);
+    // Remove windows that no longer exist in the main world. This does not rely on close events,
+    // which are missed if extraction is skipped for more than a frame.
+    let closed_windows: Vec<Entity> = extracted_windows
+        .keys()
+        .filter(|entity| !main_world_query.contains(**entity))
+        .copied()
+        .collect();
+    for closed_window in closed_windows {
+        extracted_windows.remove(&closed_window);
+        graphics_state.destroy_surface(closed_window);
+        if extracted_windows.primary == Some(closed_window) {
+            extracted_windows.primary = None;
+        }
     }
 }
@@ -2,15 +2,20 @@
 //!
 //! This module contains the [`GraphicsPlugin`] which is responsible for initializing rendering with [`wgpu`](https://docs.rs/wgpu/latest/wgpu/index.html).
 
+use crate::client::core::graphics::events::RenderErrorEvent;
 use crate::client::core::graphics::extract::camera::CameraExtractPlugin;
 use crate::client::core::graphics::extract::window::WindowExtractPlugin;
 use crate::client::core::graphics::rendering::RenderingPlugin;
-use crate::client::core::graphics::resources::{GraphicsState, MainWorld, ScratchMainWorld};
+use crate::client::core::graphics::resources::{
+    GraphicsState, MainWorld, RenderErrorReceiver, RenderErrorSender, ScratchMainWorld,
+    WindowSurfaceCapabilities,
+};
 use crate::client::core::graphics::systems::{
-    cond_surface_needs_configuration, rec_apply_commands, rp_configure_surfaces,
+    cond_surface_needs_configuration, e_update_surface_capabilities, rec_apply_commands,
+    rp_configure_surfaces, u_receive_render_errors,
 };
 use crate::client::core::window::WindowPlugin;
-use bevy_app::{App, AppLabel, Plugin, SubApp};
+use bevy_app::{App, AppLabel, Plugin, SubApp, Update};
 use bevy_ecs::prelude::{Schedule, SystemSet, World};
 use bevy_ecs::schedule::{
     IntoSystemConfigs, IntoSystemSetConfigs, ScheduleBuildSettings, ScheduleLabel,
@@ -18,6 +23,7 @@ use bevy_ecs::schedule::{
 
 mod adapter_selection_utils;
 pub mod camera;
+pub mod events;
 pub mod extract;
 pub mod pipelined_rendering;
 mod rendering;
@@ -59,6 +65,14 @@ impl Plugin for GraphicsPlugin {
         }
 
         app.init_resource::<ScratchMainWorld>();
+        app.init_resource::<WindowSurfaceCapabilities>();
+
+        // Errors from the render world are sent through this channel, which works whether or not
+        // the render app runs on another thread
+        let (render_error_sender, render_error_receiver) = async_channel::unbounded();
+        app.add_event::<RenderErrorEvent>()
+            .insert_resource(RenderErrorReceiver(render_error_receiver))
+            .add_systems(Update, u_receive_render_errors);
 
         let mut extract_schedule = Schedule::new(ExtractSchedule);
         extract_schedule.set_build_settings(ScheduleBuildSettings {
@@ -74,6 +88,7 @@ impl Plugin for GraphicsPlugin {
             .add_schedule(Render::schedule())
             .add_schedule(extract_schedule)
             .insert_resource(pollster::block_on(GraphicsState::new()))
+            .insert_resource(RenderErrorSender(render_error_sender))
             .add_systems(
                 Render,
                 (
@@ -86,6 +101,7 @@ impl Plugin for GraphicsPlugin {
                     World::clear_entities.in_set(RenderSet::CleanUp),
                 ),
             )
+            .add_systems(ExtractSchedule, e_update_surface_capabilities)
             .add_plugins(RenderingPlugin);
 
         let render_app = SubApp::new(render_app_inner, extract);
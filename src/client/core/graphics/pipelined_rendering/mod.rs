@@ -1,14 +1,19 @@
 //! Contains the [`PipelinedRenderingPlugin`].
 
+use crate::client::core::graphics::resources::GraphicsState;
 use crate::client::core::graphics::RenderSubApp;
-use async_channel::{Receiver, Sender};
+use async_channel::{Receiver, Sender, TryRecvError};
 use bevy_app::{App, AppExit, AppLabel, Plugin, SubApp};
 use bevy_ecs::change_detection::Mut;
 use bevy_ecs::prelude::World;
 use bevy_ecs::schedule::MainThreadExecutor;
 use bevy_ecs::system::Resource;
 use bevy_tasks::ComputeTaskPool;
-use tracing::debug;
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info};
 
 /// This plugin manages the pipelined rendering.
 ///
@@ -23,15 +28,100 @@ use tracing::debug;
 /// |         | extract commands | rendering schedule                    |
 /// |--------------------------------------------------------------------|
 /// ```
-pub struct PipelinedRenderingPlugin;
+///
+/// Pipelining can be disabled with [`enabled`](PipelinedRenderingPlugin::enabled) or by setting the
+/// [`DISABLE_PIPELINED_RENDERING_ENV`] environment variable. The render sub app then runs extract
+/// and its schedules on the main thread every frame, which is easier to debug.
+pub struct PipelinedRenderingPlugin {
+    /// Whether the render sub app runs on a separate thread
+    pub enabled: bool,
+    /// The name of the spawned render thread, shown in debuggers and profilers
+    pub thread_name: String,
+    /// Whether a panic on the render thread is re-raised on the main thread.
+    ///
+    /// The panic is always logged. If this is false, the app exits with [`AppExit`] instead.
+    pub propagate_panics: bool,
+    /// What the main thread does when the render thread is still busy with the previous frame
+    ///
+    /// See [`RenderBackpressure`] for more information.
+    pub backpressure: RenderBackpressure,
+}
+
+impl Default for PipelinedRenderingPlugin {
+    fn default() -> Self {
+        PipelinedRenderingPlugin {
+            enabled: true,
+            thread_name: "Render Thread".to_string(),
+            propagate_panics: false,
+            backpressure: RenderBackpressure::default(),
+        }
+    }
+}
+
+/// What the main thread does in extract when the render thread has not finished the previous frame yet
+///
+/// How often each case happens is counted in [`PipelinedRenderingDiagnostics`].
+#[allow(dead_code)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RenderBackpressure {
+    /// Wait for the render thread, so the main thread never runs more than one frame ahead
+    #[default]
+    Block,
+    /// Skip extraction for this frame and let the main thread continue.
+    ///
+    /// The render world does not see the main world changes of skipped frames. State is caught up
+    /// on the next extraction, but events are only kept for two frames, so extract systems reading
+    /// events miss them when several frames in a row are skipped.
+    Skip,
+}
+
+/// Counts how often the render thread was the bottleneck and how long each thread takes per frame
+///
+/// Since the main thread and the render thread run at the same time, comparing
+/// [`main_time`](PipelinedRenderingDiagnostics::main_time) and
+/// [`render_time`](PipelinedRenderingDiagnostics::render_time) shows which one limits the frame rate.
+///
+/// When pipelining is disabled, the resource still exists but every field stays zero.
+#[derive(Resource, Default, Debug)]
+pub struct PipelinedRenderingDiagnostics {
+    /// The number of frames where extraction was skipped because the render thread was busy
+    pub skipped_frames: u64,
+    /// The number of frames where the main thread had to wait for the render thread
+    pub blocked_frames: u64,
+    /// How long the main thread took between the last two extractions (window events and the main schedule)
+    pub main_time: Duration,
+    /// How long the render thread took to run the render schedules of the last received frame
+    pub render_time: Duration,
+    /// When the last extraction finished
+    last_extract: Option<Instant>,
+}
+
+/// Setting this environment variable (to any value) disables pipelined rendering
+pub const DISABLE_PIPELINED_RENDERING_ENV: &str = "SMVE_NO_PIPELINED_RENDERING";
+
+impl PipelinedRenderingPlugin {
+    /// Returns whether the render sub app should be moved to a separate thread
+    fn is_enabled(&self) -> bool {
+        self.enabled && std::env::var_os(DISABLE_PIPELINED_RENDERING_ENV).is_none()
+    }
+}
 
 impl Plugin for PipelinedRenderingPlugin {
     fn build(&self, app: &mut App) {
+        // Always available, so systems reading it also work while pipelining is disabled
+        app.init_resource::<PipelinedRenderingDiagnostics>();
+
         // If render app doesn't exist, don't do anything with pipelined rendering
         if app.get_sub_app(RenderSubApp).is_err() {
             return;
         }
 
+        // If pipelining is disabled, leave the render sub app where it is so it runs inline
+        if !self.is_enabled() {
+            info!("Pipelined rendering is disabled, rendering on the main thread");
+            return;
+        }
+
         // This is used in the extract to receive the render app onto the main thread.
         app.insert_resource(MainThreadExecutor::new());
 
@@ -41,14 +131,16 @@ impl Plugin for PipelinedRenderingPlugin {
     }
 
     fn cleanup(&self, app: &mut App) {
-        // Don't continue if render app doesn't exist
-        if app.get_sub_app(RenderSubApp).is_err() {
+        // Don't continue if render app doesn't exist or pipelining is disabled
+        if app.get_sub_app(RenderSubApp).is_err() || !self.is_enabled() {
             return;
         }
 
         // Create the channels for sending and receiving the render app between threads
-        let (app_to_render_sender, app_to_render_receiver) = async_channel::bounded::<SubApp>(1);
-        let (render_to_app_sender, render_to_app_receiver) = async_channel::bounded::<SubApp>(1);
+        let (app_to_render_sender, app_to_render_receiver) =
+            async_channel::bounded::<RenderThreadMessage>(1);
+        let (render_to_app_sender, render_to_app_receiver) =
+            async_channel::bounded::<RenderedApp>(1);
 
         let mut render_app = app
             .remove_sub_app(RenderSubApp)
@@ -66,18 +158,27 @@ impl Plugin for PipelinedRenderingPlugin {
         // but obviously the render thread won't have run yet. So we are using this sender to fool it
         // into thinking that it came from the render thread.
         render_to_app_sender
-            .send_blocking(render_app)
+            .send_blocking(RenderedApp {
+                app: render_app,
+                render_time: Duration::ZERO,
+            })
             .expect("Channel should not be closed.");
 
+        // The render thread stores the payload here if it panics so the main thread can re-raise it
+        let render_panic = Arc::new(Mutex::new(None));
+
         // Add the app sender and receivers to the main world
         app.insert_resource(RenderAppChannels::new(
             app_to_render_sender,
             render_to_app_receiver,
+            render_panic.clone(),
+            self.propagate_panics,
+            self.backpressure,
         ));
 
         // Start the render thread
         std::thread::Builder::new()
-            .name("Render Thread".to_string())
+            .name(self.thread_name.clone())
             .spawn(move || {
                 #[cfg(feature = "trace")]
                 let _span = tracing::info_span!("render thread").entered();
@@ -91,20 +192,47 @@ impl Plugin for PipelinedRenderingPlugin {
                         })
                         .pop();
 
-                    let Some(Ok(mut render_app)) = sent_app else {
+                    let Some(Ok(message)) = sent_app else {
                         break;
                     };
 
+                    let mut render_app = match message {
+                        RenderThreadMessage::Render(render_app) => render_app,
+                        RenderThreadMessage::Shutdown(render_app) => {
+                            // Hand the app straight back so it is cleaned up and dropped on the main thread
+                            render_to_app_sender
+                                .send_blocking(RenderedApp {
+                                    app: render_app,
+                                    render_time: Duration::ZERO,
+                                })
+                                .ok();
+                            break;
+                        }
+                    };
+
                     // Runs the render schedules
-                    {
+                    let render_start = Instant::now();
+                    let result = {
                         #[cfg(feature = "trace")]
                         let _sub_app_span =
                             tracing::info_span!("sub app", name = ?RenderSubApp).entered();
-                        render_app.app.update();
+                        std::panic::catch_unwind(AssertUnwindSafe(|| render_app.app.update()))
+                    };
+
+                    // The panic hook has already logged the payload, so hand it to the main thread.
+                    // Breaking drops the sender, which tells the main thread that the render thread is gone.
+                    if let Err(payload) = result {
+                        error!("Render thread stopped after a panic");
+                        *render_panic.lock().unwrap_or_else(|e| e.into_inner()) = Some(payload);
+                        break;
                     }
 
                     // Send it back to the main thread once we have finished rendering
-                    if render_to_app_sender.send_blocking(render_app).is_err() {
+                    let rendered_app = RenderedApp {
+                        app: render_app,
+                        render_time: render_start.elapsed(),
+                    };
+                    if render_to_app_sender.send_blocking(rendered_app).is_err() {
                         break;
                     }
                 }
@@ -122,17 +250,50 @@ impl Plugin for PipelinedRenderingPlugin {
 /// It receives the render app from the render thread, runs its extract and sends it back to
 /// the render thread.
 fn renderer_extract(world: &mut World, _app: &mut App) {
+    // Everything the main thread did since the last extraction
+    let mut diagnostics = world.resource_mut::<PipelinedRenderingDiagnostics>();
+    if let Some(last_extract) = diagnostics.last_extract {
+        diagnostics.main_time = last_extract.elapsed();
+    }
+
     // Get both the executor and the channels from the main world
     world.resource_scope(|world, main_thread_executor: Mut<MainThreadExecutor>| {
         world.resource_scope(|world, mut render_channels: Mut<RenderAppChannels>| {
-            // Receive the render app from the render thread
-            if let Some(mut render_app) = ComputeTaskPool::get()
-                .scope_with_executor(true, Some(&*main_thread_executor.0), |s| {
-                    s.spawn(async { render_channels.recv().await });
-                })
-                .pop()
-                .expect("Render app should exist")
+            // Receive the render app from the render thread, if it has finished the previous frame
+            let render_app = match render_channels.try_recv() {
+                Ok(render_app) => Some(render_app),
+                Err(TryRecvError::Closed) => None,
+                Err(TryRecvError::Empty) => {
+                    let mut diagnostics = world.resource_mut::<PipelinedRenderingDiagnostics>();
+                    if render_channels.backpressure == RenderBackpressure::Skip {
+                        diagnostics.skipped_frames += 1;
+
+                        // The render thread might be waiting on systems that have to run on the main thread
+                        if let Some(ticker) = main_thread_executor.0.ticker() {
+                            while ticker.try_tick() {}
+                        }
+                        return;
+                    }
+                    diagnostics.blocked_frames += 1;
+
+                    ComputeTaskPool::get()
+                        .scope_with_executor(true, Some(&*main_thread_executor.0), |s| {
+                            s.spawn(async { render_channels.recv().await });
+                        })
+                        .pop()
+                        .expect("Render app should exist")
+                }
+            };
+
+            if let Some(RenderedApp {
+                app: mut render_app,
+                render_time,
+            }) = render_app
             {
+                world
+                    .resource_mut::<PipelinedRenderingDiagnostics>()
+                    .render_time = render_time;
+
                 // Extract objects from main world to render world
                 render_app.extract(world);
 
@@ -140,10 +301,35 @@ fn renderer_extract(world: &mut World, _app: &mut App) {
                 render_channels.send_blocking(render_app);
             } else {
                 // Render thread has panicked.
+                if render_channels.propagate_panics {
+                    if let Some(payload) = render_channels.take_panic() {
+                        std::panic::resume_unwind(payload);
+                    }
+                }
                 world.send_event(AppExit);
             }
         });
     });
+
+    world
+        .resource_mut::<PipelinedRenderingDiagnostics>()
+        .last_extract = Some(Instant::now());
+}
+
+/// A message sent from the main thread to the render thread
+enum RenderThreadMessage {
+    /// Run the render schedules on the extracted render app
+    Render(SubApp),
+    /// Send the render app back and exit the render thread
+    Shutdown(SubApp),
+}
+
+/// The render app sent back from the render thread to the main thread
+struct RenderedApp {
+    /// The render app
+    app: SubApp,
+    /// How long the render schedules took to run
+    render_time: Duration,
 }
 
 /// Sub app label for the pipelined rendering app
@@ -156,46 +342,115 @@ pub struct PipelinedRenderingApp;
 #[derive(Resource)]
 pub struct RenderAppChannels {
     /// Sender used to send the render app to the render thread
-    app_to_render_sender: Sender<SubApp>,
+    app_to_render_sender: Sender<RenderThreadMessage>,
     /// Receiver used to receive the render app from the render thread
-    render_to_app_receiver: Receiver<SubApp>,
+    render_to_app_receiver: Receiver<RenderedApp>,
     /// Used on [`Drop`] to receive the render app back before dropping it
     render_app_in_render_thread: bool,
+    /// The payload of the render thread's panic, if it has panicked
+    render_panic: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
+    /// Whether the render thread's panic is re-raised on the main thread
+    propagate_panics: bool,
+    /// What to do when the render app has not been sent back yet
+    backpressure: RenderBackpressure,
 }
 
 impl RenderAppChannels {
     /// Create a `RenderAppChannels` from a [`Receiver`] and [`Sender`]
-    fn new(app_to_render_sender: Sender<SubApp>, render_to_app_receiver: Receiver<SubApp>) -> Self {
+    fn new(
+        app_to_render_sender: Sender<RenderThreadMessage>,
+        render_to_app_receiver: Receiver<RenderedApp>,
+        render_panic: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
+        propagate_panics: bool,
+        backpressure: RenderBackpressure,
+    ) -> Self {
         RenderAppChannels {
             app_to_render_sender,
             render_to_app_receiver,
             render_app_in_render_thread: false,
+            render_panic,
+            propagate_panics,
+            backpressure,
         }
     }
 
+    /// Takes the payload of the render thread's panic, if it has panicked
+    fn take_panic(&self) -> Option<Box<dyn Any + Send>> {
+        self.render_panic
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
     /// Blocks while sending the render app back to the render thread
     fn send_blocking(&mut self, render_app: SubApp) {
         self.app_to_render_sender
-            .send_blocking(render_app)
+            .send_blocking(RenderThreadMessage::Render(render_app))
             .expect("Channel should not be closed.");
         self.render_app_in_render_thread = true;
     }
 
+    /// Receives the render app back from the render thread without waiting
+    fn try_recv(&mut self) -> Result<RenderedApp, TryRecvError> {
+        let rendered_app = self.render_to_app_receiver.try_recv()?;
+        self.render_app_in_render_thread = false;
+        Ok(rendered_app)
+    }
+
     /// Asynchronously receives the render app back from the render thread
-    async fn recv(&mut self) -> Option<SubApp> {
-        let render_app = self.render_to_app_receiver.recv().await.ok()?;
+    async fn recv(&mut self) -> Option<RenderedApp> {
+        let rendered_app = self.render_to_app_receiver.recv().await.ok()?;
         self.render_app_in_render_thread = false;
-        Some(render_app)
+        Some(rendered_app)
     }
 }
 
 impl Drop for RenderAppChannels {
     fn drop(&mut self) {
-        if self.render_app_in_render_thread {
-            // Non-send data in the render world was initialized on the main thread
-            // So when the app ends, we receive it back so that the drop methods runs on the right
-            // thread. (From bevy)
-            self.render_to_app_receiver.recv_blocking().ok();
+        let rendered_app = if self.render_app_in_render_thread {
+            // Wait for the render thread to finish the frame it is rendering.
+            // If the render thread has panicked, there is nothing to shut down.
+            self.render_to_app_receiver.recv_blocking().ok()
+        } else {
+            // The render app is either still waiting in the channel before the first extract, or
+            // it is owned by an extract that is unwinding, in which case it is already gone and
+            // dropping the sender lets the render thread exit.
+            self.render_to_app_receiver.try_recv().ok()
+        };
+        let Some(RenderedApp {
+            app: render_app, ..
+        }) = rendered_app
+        else {
+            return;
+        };
+
+        // Tell the render thread to exit
+        if self
+            .app_to_render_sender
+            .send_blocking(RenderThreadMessage::Shutdown(render_app))
+            .is_err()
+        {
+            return;
+        }
+
+        // Non-send data in the render world was initialized on the main thread
+        // So when the app ends, we receive it back so that the drop methods runs on the right
+        // thread. (From bevy)
+        let Ok(RenderedApp {
+            app: mut render_app,
+            ..
+        }) = self.render_to_app_receiver.recv_blocking()
+        else {
+            return;
+        };
+
+        // Flush GPU work and tear down the surfaces here, since they were created on the main thread
+        if let Some(mut graphics_state) = render_app
+            .app
+            .world
+            .get_resource_mut::<GraphicsState<'static>>()
+        {
+            graphics_state.shutdown();
         }
     }
 }
@@ -1,16 +1,21 @@
 //! Contains wgpu code for rendering
 
-use crate::client::core::graphics::camera::components::Camera;
+use crate::client::core::graphics::camera::components::{Camera, CameraRenderTarget};
+use crate::client::core::graphics::camera::resources::ClearColor;
 use crate::client::core::graphics::rendering::components::SurfaceTextureComponent;
 use crate::client::core::graphics::rendering::resources::CommandEncoderWrapper;
-use crate::client::core::graphics::rendering::utils::begin_render_pass;
-use crate::client::core::graphics::resources::{ExtractedWindows, GraphicsState};
+use crate::client::core::graphics::rendering::utils::{
+    begin_render_pass, begin_render_pass_on_view,
+};
+use crate::client::core::graphics::resources::{
+    ExtractedWindows, GraphicsState, RenderError, RenderErrorSender,
+};
 use bevy_ecs::entity::Entity;
 use bevy_ecs::prelude::Query;
 use bevy_ecs::system::{Commands, Res, ResMut, SystemState};
 use bevy_ecs::world::World;
 use std::ops::DerefMut;
-use tracing::{error, warn};
+use tracing::warn;
 use wgpu::{CommandEncoderDescriptor, SurfaceError};
 
 /// Begins the render pass through the command encoder
@@ -19,11 +24,29 @@ use wgpu::{CommandEncoderDescriptor, SurfaceError};
 pub fn rpq_begin_render_passes(
     cameras: Query<(Entity, &Camera)>,
     extracted_windows: Res<ExtractedWindows>,
+    clear_color: Res<ClearColor>,
     mut graphics_state: ResMut<GraphicsState<'static>>,
     mut command_encoder: ResMut<CommandEncoderWrapper>,
+    render_errors: Res<RenderErrorSender>,
     mut commands: Commands,
 ) {
     for (entity, camera) in cameras.iter() {
+        // Offscreen render targets do not have a surface texture to present
+        if let CameraRenderTarget::Texture(name) = &camera.render_target {
+            if let Some(render_target) = graphics_state.render_targets.get(name) {
+                begin_render_pass_on_view(
+                    name,
+                    &render_target.view,
+                    command_encoder.deref_mut(),
+                    &camera.clear_behaviour,
+                    **clear_color,
+                );
+            } else {
+                warn!("No render target named {name}, skipping camera {entity:?}");
+            }
+            continue;
+        }
+
         let Some(render_window) = camera
             .render_target
             .get_window_entity(extracted_windows.primary)
@@ -39,6 +62,7 @@ pub fn rpq_begin_render_passes(
                 &surface_state.surface,
                 command_encoder.deref_mut(),
                 &camera.clear_behaviour,
+                **clear_color,
             ) {
                 Ok(surface_texture) => {
                     commands
@@ -51,8 +75,11 @@ pub fn rpq_begin_render_passes(
                 Err(SurfaceError::OutOfMemory) => {
                     panic!("Out of memory!");
                 }
-                Err(e) => {
-                    error!("Surface error! {}", e);
+                Err(error) => {
+                    render_errors.send(RenderError::Surface {
+                        window: render_window,
+                        error,
+                    });
                 }
             }
         } else {
@@ -1,27 +1,46 @@
 //! Utility functions for rendering
 
 use crate::client::core::graphics::camera::components::CameraClearBehaviour;
-use wgpu::{CommandEncoder, LoadOp, Surface, SurfaceError, SurfaceTexture};
+use wgpu::{Color, CommandEncoder, LoadOp, Surface, SurfaceError, SurfaceTexture, TextureView};
 
 /// Writes the command to begin a render pass for the surface supplied
+///
+/// `clear_color` is used when `clear_behaviour` is [`CameraClearBehaviour::Default`].
 pub fn begin_render_pass(
     id: &str,
     surface: &Surface,
     command_encoder: &mut CommandEncoder,
     clear_behaviour: &CameraClearBehaviour,
+    clear_color: Color,
 ) -> Result<SurfaceTexture, SurfaceError> {
     let output = surface.get_current_texture()?;
     let view = output
         .texture
         .create_view(&wgpu::TextureViewDescriptor::default());
+    begin_render_pass_on_view(id, &view, command_encoder, clear_behaviour, clear_color);
+
+    Ok(output)
+}
+
+/// Writes the command to begin a render pass for the texture view supplied
+///
+/// Used directly for offscreen render targets, which do not need to acquire a texture first.
+pub fn begin_render_pass_on_view(
+    id: &str,
+    view: &TextureView,
+    command_encoder: &mut CommandEncoder,
+    clear_behaviour: &CameraClearBehaviour,
+    clear_color: Color,
+) {
     {
         let _render_pass = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some(format!("Render Pass {id}").as_str()),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: &view,
+                view,
                 resolve_target: None,
                 ops: wgpu::Operations {
                     load: match clear_behaviour {
+                        CameraClearBehaviour::Default => LoadOp::Clear(clear_color),
                         CameraClearBehaviour::DontClear => LoadOp::Load,
                         CameraClearBehaviour::Color(color) => LoadOp::Clear(*color),
                     },
@@ -33,6 +52,4 @@ pub fn begin_render_pass(
             timestamp_writes: None,
         });
     }
-
-    Ok(output)
 }
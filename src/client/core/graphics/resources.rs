@@ -4,15 +4,18 @@
 
 use crate::client::core::graphics::adapter_selection_utils::get_best_adapter;
 use crate::client::core::graphics::extract::window::ExtractedWindow;
-use crate::client::core::window::components::RawHandleWrapper;
+use crate::client::core::window::components::{PresentMode, RawHandleWrapper, SurfaceFormat};
+use async_channel::{Receiver, Sender};
 use bevy_ecs::entity::{Entity, EntityHashMap};
 use bevy_ecs::system::Resource;
 use bevy_ecs::world::World;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
-use tracing::info;
-use wgpu::{Backends, CreateSurfaceError, PresentMode};
+use tracing::{error, info, warn};
+use wgpu::{Backends, CreateSurfaceError};
 use winit::dpi::PhysicalSize;
 
 /// Contains the global and per-window objects needed for rendering.
@@ -34,6 +37,13 @@ pub struct GraphicsState<'window> {
     // Per-Window Objects
     /// Contains a mapping from the window id to the surface state.
     pub surface_states: HashMap<Entity, SurfaceState<'window>>,
+    /// Incremented whenever a surface is created or destroyed, so systems can tell when the
+    /// surfaces (and their capabilities) have changed.
+    pub surface_generation: u64,
+
+    // Offscreen Objects
+    /// Contains a mapping from the name of an offscreen render target to the render target.
+    pub render_targets: HashMap<String, RenderTarget>,
     //_not_send_sync: PhantomData<*const ()>,
 }
 
@@ -56,19 +66,9 @@ impl<'window> GraphicsState<'window> {
         info!("Selected Adapter: {:?}", adapter.get_info());
 
         // Create device
-        let (device, queue) = adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    required_features: wgpu::Features::empty(),
-                    required_limits: wgpu::Limits::default(),
-                    label: None,
-                },
-                None,
-            )
-            .await
-            .unwrap_or_else(|err| {
-                panic!("Failed to create device: {err}");
-            });
+        let (device, queue) = request_device(&adapter).await.unwrap_or_else(|err| {
+            panic!("Failed to create device: {err}");
+        });
 
         Self {
             instance,
@@ -76,6 +76,8 @@ impl<'window> GraphicsState<'window> {
             queue,
             adapter,
             surface_states: HashMap::new(),
+            surface_generation: 0,
+            render_targets: HashMap::new(),
             //_not_send_sync: PhantomData,
         }
     }
@@ -111,23 +113,18 @@ impl<'window> GraphicsState<'window> {
         let surface = self.instance.create_surface(handle)?;
 
         let surface_caps = surface.get_capabilities(&self.adapter);
-        // Gets the first surface format that is sRGB, otherwise use the first surface format returned
-        let surface_format = surface_caps
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(surface_caps.formats[0]);
+        let capabilities = SurfaceCapabilities::from(&surface_caps);
+        let surface_format =
+            select_surface_format(window_component.surface_format, &surface_caps.formats);
         let config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             format: surface_format,
             width: window_component.physical_width,
             height: window_component.physical_height,
-            present_mode: if window_component.vsync {
-                PresentMode::AutoVsync
-            } else {
-                PresentMode::AutoNoVsync
-            },
+            present_mode: select_present_mode(
+                window_component.present_mode,
+                &capabilities.present_modes,
+            ),
             desired_maximum_frame_latency: 2,
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
@@ -139,12 +136,15 @@ impl<'window> GraphicsState<'window> {
             SurfaceState {
                 surface,
                 config,
+                capabilities,
+                present_mode: window_component.present_mode,
                 size: PhysicalSize::new(
                     window_component.physical_width,
                     window_component.physical_height,
                 ),
             },
         );
+        self.surface_generation += 1;
 
         Ok(())
     }
@@ -155,8 +155,258 @@ impl<'window> GraphicsState<'window> {
     /// - `entity` - The entity corresponding to the surface to be destroyed
     pub fn destroy_surface(&mut self, entity: Entity) {
         self.surface_states.remove(&entity);
+        self.surface_generation += 1;
         info!("Surface destroyed for entity {:?}", entity);
     }
+
+    #[allow(dead_code)]
+    /// Creates an offscreen render target that cameras can render to.
+    ///
+    /// Replaces the render target with the same name if it already exists.
+    ///
+    /// # Arguments
+    /// - `name` - The name used to refer to the render target, for example in [`CameraRenderTarget::Texture`](crate::client::core::graphics::camera::components::CameraRenderTarget::Texture).
+    /// - `width` - The width of the texture in pixels.
+    /// - `height` - The height of the texture in pixels.
+    /// - `format` - The texture format of the render target.
+    ///
+    /// # Returns
+    /// A reference to the created render target.
+    pub fn create_render_target(
+        &mut self,
+        name: impl Into<String>,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+    ) -> &RenderTarget {
+        let name = name.into();
+        let size = PhysicalSize::new(width.max(1), height.max(1));
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(format!("Render Target {name}").as_str()),
+            size: wgpu::Extent3d {
+                width: size.width,
+                height: size.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        info!("Render target {name} created with size {width}x{height}");
+
+        self.render_targets.insert(
+            name.clone(),
+            RenderTarget {
+                texture,
+                view,
+                format,
+                size,
+            },
+        );
+        &self.render_targets[&name]
+    }
+
+    #[allow(dead_code)]
+    /// Destroys an offscreen render target.
+    ///
+    /// # Arguments
+    /// - `name` - The name of the render target to be destroyed
+    pub fn destroy_render_target(&mut self, name: &str) {
+        if self.render_targets.remove(name).is_some() {
+            info!("Render target {name} destroyed");
+        }
+    }
+
+    #[allow(dead_code)]
+    /// Lists the adapters that can be switched to with [`switch_adapter`](GraphicsState::switch_adapter).
+    ///
+    /// # Returns
+    /// The information of every adapter found, the index in this list is used to select an adapter.
+    pub fn available_adapters(&self) -> Vec<wgpu::AdapterInfo> {
+        self.instance
+            .enumerate_adapters(Backends::all())
+            .iter()
+            .map(|adapter| adapter.get_info())
+            .collect()
+    }
+
+    #[allow(dead_code)]
+    /// Switches to another adapter, recreating the device and queue.
+    ///
+    /// All surfaces are destroyed and are recreated on the new device by `rp_configure_surfaces` on the next frame.
+    /// Any other GPU resources created with the old device have to be recreated by their owners.
+    ///
+    /// # Arguments
+    /// - `index` - The index of the adapter in [`available_adapters`](GraphicsState::available_adapters).
+    ///
+    /// # Returns
+    /// An empty result if the switch succeeded, otherwise an [`AdapterSwitchError`] is returned and the old adapter is kept.
+    pub async fn switch_adapter(&mut self, index: usize) -> Result<(), AdapterSwitchError> {
+        let mut adapters = self.instance.enumerate_adapters(Backends::all());
+        if index >= adapters.len() {
+            error!("Failed to switch adapter: no adapter at index {index}");
+            return Err(AdapterSwitchError::InvalidIndex(index));
+        }
+        let adapter = adapters.swap_remove(index);
+
+        // The surfaces are recreated on the new adapter, so it has to be able to present to every window
+        for (entity, surface_state) in &self.surface_states {
+            if !adapter.is_surface_supported(&surface_state.surface)
+                || surface_state
+                    .surface
+                    .get_capabilities(&adapter)
+                    .formats
+                    .is_empty()
+            {
+                error!(
+                    "Failed to switch adapter: {:?} cannot present to window on {:?}, keeping the current adapter",
+                    adapter.get_info(),
+                    entity
+                );
+                return Err(AdapterSwitchError::UnsupportedSurface(*entity));
+            }
+        }
+
+        let (device, queue) = request_device(&adapter).await.map_err(|err| {
+            error!(
+                "Failed to create device on {:?}, keeping the current adapter: {err}",
+                adapter.get_info()
+            );
+            AdapterSwitchError::RequestDevice(err)
+        })?;
+
+        // Render targets belong to the old device, so they are recreated with the same parameters
+        let render_targets: Vec<_> = self
+            .render_targets
+            .iter()
+            .map(|(name, render_target)| (name.clone(), render_target.size, render_target.format))
+            .collect();
+
+        // Finish the work on the old device before its surfaces are dropped
+        self.shutdown();
+
+        self.adapter = adapter;
+        self.device = device;
+        self.queue = queue;
+
+        for (name, size, format) in render_targets {
+            self.create_render_target(name, size.width, size.height, format);
+        }
+
+        info!("Switched to adapter: {:?}", self.adapter.get_info());
+
+        Ok(())
+    }
+
+    /// Waits for all submitted GPU work to finish and destroys every surface and render target.
+    ///
+    /// Called before the renderer is dropped so that no work is in flight when the device is destroyed.
+    pub fn shutdown(&mut self) {
+        self.device.poll(wgpu::Maintain::Wait);
+        self.surface_states.clear();
+        self.surface_generation += 1;
+        self.render_targets.clear();
+        info!("Graphics state shut down");
+    }
+}
+
+/// An offscreen texture that cameras can render to instead of a window.
+///
+/// This will be stored in the [`GraphicsState`] struct by name.
+#[allow(dead_code)]
+pub struct RenderTarget {
+    /// The texture rendered to.
+    pub texture: wgpu::Texture,
+    /// A view of the whole texture, used as the color attachment.
+    pub view: wgpu::TextureView,
+    /// The format of the texture.
+    pub format: wgpu::TextureFormat,
+    /// The size of the texture.
+    pub size: PhysicalSize<u32>,
+}
+
+/// Requests a device and queue from an adapter with the features and limits the renderer needs.
+async fn request_device(
+    adapter: &wgpu::Adapter,
+) -> Result<(wgpu::Device, wgpu::Queue), wgpu::RequestDeviceError> {
+    adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                required_features: wgpu::Features::empty(),
+                required_limits: wgpu::Limits::default(),
+                label: None,
+            },
+            None,
+        )
+        .await
+}
+
+/// Errors that can happen when switching adapters with [`GraphicsState::switch_adapter`]
+#[allow(dead_code)]
+pub enum AdapterSwitchError {
+    /// Error when there is no adapter at the index
+    InvalidIndex(usize),
+    /// Error on failure to create a device on the new adapter
+    RequestDevice(wgpu::RequestDeviceError),
+    /// Error when the new adapter cannot present to the surface of a window
+    UnsupportedSurface(Entity),
+}
+
+impl Debug for AdapterSwitchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdapterSwitchError::InvalidIndex(index) => {
+                write!(f, "There is no adapter at index {:?}", index)
+            }
+            AdapterSwitchError::RequestDevice(err) => {
+                write!(f, "Failed to create device: {:?}", err)
+            }
+            AdapterSwitchError::UnsupportedSurface(entity) => {
+                write!(
+                    f,
+                    "Adapter does not support the surface of window on {:?}",
+                    entity
+                )
+            }
+        }
+    }
+}
+
+impl Display for AdapterSwitchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdapterSwitchError::InvalidIndex(index) => {
+                write!(f, "There is no adapter at index {index}")
+            }
+            AdapterSwitchError::RequestDevice(err) => {
+                write!(f, "Failed to create device: {err}")
+            }
+            AdapterSwitchError::UnsupportedSurface(entity) => {
+                write!(
+                    f,
+                    "Adapter does not support the surface of window on {:?}",
+                    entity
+                )
+            }
+        }
+    }
+}
+
+impl Error for AdapterSwitchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AdapterSwitchError::InvalidIndex(_) | AdapterSwitchError::UnsupportedSurface(_) => None,
+            AdapterSwitchError::RequestDevice(err) => Some(err),
+        }
+    }
 }
 
 /// Contains various values associated with a surface.
@@ -167,6 +417,12 @@ pub struct SurfaceState<'window> {
     pub surface: wgpu::Surface<'window>,
     /// The surface configuration.
     pub config: wgpu::SurfaceConfiguration,
+    /// What the surface supports on the current adapter.
+    pub capabilities: SurfaceCapabilities,
+    /// The present mode requested for the surface.
+    ///
+    /// This can differ from the present mode in [`config`](SurfaceState::config) if it is not supported.
+    pub present_mode: PresentMode,
     /// The size of the surface.
     pub size: PhysicalSize<u32>,
 }
@@ -189,6 +445,168 @@ impl SurfaceState<'_> {
             self.surface.configure(device, &self.config);
         }
     }
+
+    /// Reconfigures the surface with a new texture format.
+    ///
+    /// # Arguments
+    /// - `surface_format` - The requested format, falls back to a supported one if necessary.
+    /// - `device` - The wgpu device to configure the surface with.
+    pub fn set_format(&mut self, surface_format: SurfaceFormat, device: &wgpu::Device) {
+        self.config.format = select_surface_format(surface_format, &self.capabilities.formats);
+        self.surface.configure(device, &self.config);
+    }
+
+    /// Reconfigures the surface with a new present mode.
+    ///
+    /// # Arguments
+    /// - `present_mode` - The requested present mode, falls back to a supported one if necessary.
+    /// - `device` - The wgpu device to configure the surface with.
+    pub fn set_present_mode(&mut self, present_mode: PresentMode, device: &wgpu::Device) {
+        self.present_mode = present_mode;
+        self.config.present_mode =
+            select_present_mode(present_mode, &self.capabilities.present_modes);
+        self.surface.configure(device, &self.config);
+    }
+}
+
+/// The formats, present modes and alpha modes supported by a surface.
+///
+/// A cloneable version of [`wgpu::SurfaceCapabilities`].
+#[allow(dead_code)]
+#[derive(Clone, Debug, Default)]
+pub struct SurfaceCapabilities {
+    /// Supported texture formats, the preferred format comes first
+    pub formats: Vec<wgpu::TextureFormat>,
+    /// Supported present modes
+    pub present_modes: Vec<PresentMode>,
+    /// Supported alpha modes, the preferred mode comes first
+    pub alpha_modes: Vec<wgpu::CompositeAlphaMode>,
+}
+
+impl From<&wgpu::SurfaceCapabilities> for SurfaceCapabilities {
+    fn from(capabilities: &wgpu::SurfaceCapabilities) -> Self {
+        SurfaceCapabilities {
+            formats: capabilities.formats.clone(),
+            present_modes: capabilities
+                .present_modes
+                .iter()
+                .map(|mode| match mode {
+                    wgpu::PresentMode::AutoVsync => PresentMode::AutoVsync,
+                    wgpu::PresentMode::AutoNoVsync => PresentMode::AutoNoVsync,
+                    wgpu::PresentMode::Fifo => PresentMode::Fifo,
+                    wgpu::PresentMode::FifoRelaxed => PresentMode::FifoRelaxed,
+                    wgpu::PresentMode::Immediate => PresentMode::Immediate,
+                    wgpu::PresentMode::Mailbox => PresentMode::Mailbox,
+                })
+                .collect(),
+            alpha_modes: capabilities.alpha_modes.clone(),
+        }
+    }
+}
+
+impl From<PresentMode> for wgpu::PresentMode {
+    fn from(present_mode: PresentMode) -> Self {
+        match present_mode {
+            PresentMode::AutoVsync => wgpu::PresentMode::AutoVsync,
+            PresentMode::AutoNoVsync => wgpu::PresentMode::AutoNoVsync,
+            PresentMode::Fifo => wgpu::PresentMode::Fifo,
+            PresentMode::FifoRelaxed => wgpu::PresentMode::FifoRelaxed,
+            PresentMode::Immediate => wgpu::PresentMode::Immediate,
+            PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
+        }
+    }
+}
+
+/// A resource on the main app containing the capabilities of each window's surface.
+///
+/// Use this to only offer options (such as present modes in a settings menu) that the surface
+/// actually supports. Surfaces are created in the render app, so a new window's entry appears
+/// a frame after its surface is created.
+#[derive(Default, Resource)]
+pub struct WindowSurfaceCapabilities(pub EntityHashMap<SurfaceCapabilities>);
+
+impl Deref for WindowSurfaceCapabilities {
+    type Target = EntityHashMap<SurfaceCapabilities>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for WindowSurfaceCapabilities {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Selects the texture format to configure a surface with.
+///
+/// # Arguments
+/// - `requested` - The surface format requested by the window.
+/// - `supported` - The formats supported by the surface.
+///
+/// # Returns
+/// The requested format if it is supported, otherwise the first sRGB format,
+/// otherwise the first supported format.
+fn select_surface_format(
+    requested: SurfaceFormat,
+    supported: &[wgpu::TextureFormat],
+) -> wgpu::TextureFormat {
+    let selected = match requested {
+        SurfaceFormat::Srgb => None,
+        SurfaceFormat::Linear => supported.iter().copied().find(|f| !f.is_srgb()),
+        SurfaceFormat::Exact(format) => supported.contains(&format).then_some(format),
+    };
+
+    if selected.is_none() && requested != SurfaceFormat::Srgb {
+        warn!("Surface format {requested:?} is not supported, falling back to sRGB");
+    }
+
+    // Gets the first surface format that is sRGB, otherwise use the first surface format returned
+    selected
+        .or_else(|| supported.iter().copied().find(|f| f.is_srgb()))
+        .unwrap_or(supported[0])
+}
+
+/// Selects the wgpu present mode to use for a requested [`PresentMode`].
+///
+/// # Arguments
+/// - `requested` - The present mode requested by the window.
+/// - `supported` - The present modes supported by the surface.
+///
+/// # Returns
+/// The requested present mode if it is supported, otherwise the closest supported one.
+/// The automatic modes are resolved by wgpu itself.
+fn select_present_mode(requested: PresentMode, supported: &[PresentMode]) -> wgpu::PresentMode {
+    // Fifo is guaranteed to be supported so every chain ends with it
+    let candidates: &[PresentMode] = match requested {
+        PresentMode::AutoVsync => return wgpu::PresentMode::AutoVsync,
+        PresentMode::AutoNoVsync => return wgpu::PresentMode::AutoNoVsync,
+        PresentMode::Fifo => &[PresentMode::Fifo],
+        PresentMode::FifoRelaxed => &[PresentMode::FifoRelaxed, PresentMode::Fifo],
+        PresentMode::Immediate => &[
+            PresentMode::Immediate,
+            PresentMode::Mailbox,
+            PresentMode::Fifo,
+        ],
+        PresentMode::Mailbox => &[
+            PresentMode::Mailbox,
+            PresentMode::Immediate,
+            PresentMode::Fifo,
+        ],
+    };
+
+    let selected = candidates
+        .iter()
+        .copied()
+        .find(|mode| supported.contains(mode))
+        .unwrap_or(PresentMode::Fifo);
+
+    if selected != candidates[0] {
+        warn!("Present mode {requested:?} is not supported, falling back to {selected:?}");
+    }
+
+    wgpu::PresentMode::from(selected)
 }
 
 /// A blank world to swap the actual world with during extraction to avoid constantly making new worlds
@@ -239,3 +657,60 @@ impl DerefMut for ExtractedWindows {
         &mut self.windows
     }
 }
+
+/// A recoverable error that happened in the render world
+///
+/// These are sent to the main world through [`RenderErrorSender`] and emitted as
+/// [`RenderErrorEvent`](crate::client::core::graphics::events::RenderErrorEvent)s.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub enum RenderError {
+    /// Error when acquiring the next texture of a window's surface
+    Surface {
+        /// The window the surface belongs to
+        window: Entity,
+        /// The error returned by wgpu
+        error: wgpu::SurfaceError,
+    },
+    /// Any other error, described by a message
+    Other(String),
+}
+
+impl Display for RenderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::Surface { window, error } => {
+                write!(f, "Surface error on window {:?}: {error}", window)
+            }
+            RenderError::Other(message) => {
+                write!(f, "{message}")
+            }
+        }
+    }
+}
+
+impl Error for RenderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RenderError::Surface { error, .. } => Some(error),
+            RenderError::Other(_) => None,
+        }
+    }
+}
+
+/// A resource on the render app used to report [`RenderError`]s to the main world
+#[derive(Resource)]
+pub struct RenderErrorSender(pub Sender<RenderError>);
+
+impl RenderErrorSender {
+    /// Sends an error to the main world
+    ///
+    /// The error is dropped if the main world no longer exists.
+    pub fn send(&self, error: RenderError) {
+        self.0.try_send(error).ok();
+    }
+}
+
+/// A resource on the main app that receives the [`RenderError`]s sent by the render app
+#[derive(Resource)]
+pub struct RenderErrorReceiver(pub Receiver<RenderError>);
@@ -1,14 +1,16 @@
 //! Bevy systems for the graphics module.
 
-use crate::client::core::graphics::resources::{ExtractedWindows, GraphicsState};
+use crate::client::core::graphics::events::RenderErrorEvent;
+use crate::client::core::graphics::resources::{
+    ExtractedWindows, GraphicsState, MainWorld, RenderErrorReceiver, WindowSurfaceCapabilities,
+};
 use crate::client::core::graphics::ExtractSchedule;
-use bevy_ecs::prelude::{Res, Schedules, World};
-use bevy_ecs::system::ResMut;
+use bevy_ecs::prelude::{EventWriter, Res, Schedules, World};
+use bevy_ecs::system::{Local, ResMut};
 use bevy_ecs::world::Mut;
 use cfg_if::cfg_if;
 use std::ops::DerefMut;
-use tracing::info;
-use wgpu::PresentMode;
+use tracing::{error, info};
 use winit::dpi::PhysicalSize;
 
 cfg_if! {
@@ -57,14 +59,14 @@ pub fn rp_configure_surfaces(
             );
         }
 
-        if window.present_mode_changed {
-            surface_state.config.present_mode = match window.vsync {
-                true => PresentMode::AutoVsync,
-                false => PresentMode::AutoNoVsync,
-            };
-            surface_state
-                .surface
-                .configure(&graphics_state.device, &surface_state.config);
+        // Compared against the mode the surface was configured with, so the surface itself is
+        // the single source of truth instead of a separate changed flag on the extracted window
+        if window.present_mode != surface_state.present_mode {
+            surface_state.set_present_mode(window.present_mode, &graphics_state.device);
+        }
+
+        if window.surface_format_changed {
+            surface_state.set_format(window.surface_format, &graphics_state.device);
         }
     }
 }
@@ -78,9 +80,13 @@ pub fn cond_surface_needs_configuration(
     extracted_windows: Res<ExtractedWindows>,
 ) -> bool {
     for (entity, window) in extracted_windows.iter() {
-        if !graphics_state.surface_states.contains_key(entity)
-            || window.size_changed
-            || window.present_mode_changed
+        let Some(surface_state) = graphics_state.surface_states.get(entity) else {
+            return true;
+        };
+
+        if window.size_changed
+            || window.present_mode != surface_state.present_mode
+            || window.surface_format_changed
         {
             return true;
         }
@@ -100,3 +106,43 @@ pub fn rec_apply_commands(render_world: &mut World) {
             .apply_deferred(render_world);
     });
 }
+
+/// Copies the capabilities of every surface into [`WindowSurfaceCapabilities`] on the main world
+///
+/// Called on `Extract`. Only touches the main world resource when a surface was created or destroyed
+/// so that change detection on it stays meaningful. Surfaces recreated under the same entity (for
+/// example after switching adapters) are caught by [`GraphicsState::surface_generation`].
+pub fn e_update_surface_capabilities(
+    graphics_state: Res<GraphicsState<'static>>,
+    mut main_world: ResMut<MainWorld>,
+    mut synced_generation: Local<Option<u64>>,
+) {
+    if *synced_generation == Some(graphics_state.surface_generation) {
+        return;
+    }
+
+    let Some(mut window_capabilities) = main_world.get_resource_mut::<WindowSurfaceCapabilities>()
+    else {
+        return;
+    };
+    *synced_generation = Some(graphics_state.surface_generation);
+
+    window_capabilities.0 = graphics_state
+        .surface_states
+        .iter()
+        .map(|(entity, surface_state)| (*entity, surface_state.capabilities.clone()))
+        .collect();
+}
+
+/// Logs the errors reported by the render world and emits them as [`RenderErrorEvent`]s
+///
+/// Runs on `Update` in the main world
+pub fn u_receive_render_errors(
+    receiver: Res<RenderErrorReceiver>,
+    mut render_error_events: EventWriter<RenderErrorEvent>,
+) {
+    while let Ok(error) = receiver.0.try_recv() {
+        error!("Render error: {error}");
+        render_error_events.send(RenderErrorEvent { error });
+    }
+}
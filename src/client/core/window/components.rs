@@ -32,8 +32,20 @@ pub struct Window {
     /// A flat vector of RGBA data of the icon
     /// `None` if there is no icon
     pub icon_data: Option<Vec<u8>>,
-    /// Whether vsync is enabled
-    pub vsync: bool,
+    /// How frames are presented to the window
+    ///
+    /// # See Also
+    /// [`PresentMode`]
+    pub present_mode: PresentMode,
+    /// The texture format of the window's surface
+    ///
+    /// # See Also
+    /// [`SurfaceFormat`]
+    pub surface_format: SurfaceFormat,
+    /// The monitor the window should be on
+    ///
+    /// `None` lets the platform decide. Setting this moves the window to the centre of the monitor.
+    pub preferred_monitor: Option<MonitorId>,
 }
 
 impl Default for Window {
@@ -44,18 +56,91 @@ impl Default for Window {
             icon_width: icon::IMAGE_WIDTH as u32,
             icon_height: icon::IMAGE_HEIGHT as u32,
             icon_data: Some(icon::IMAGE_DATA.to_vec()),
-            vsync: true,
+            present_mode: PresentMode::AutoVsync,
+            surface_format: SurfaceFormat::Srgb,
+            preferred_monitor: None,
         }
     }
 }
 
+#[allow(dead_code)]
+impl Window {
+    /// Returns whether the present mode waits for vertical blanks
+    pub fn vsync(&self) -> bool {
+        matches!(
+            self.present_mode,
+            PresentMode::AutoVsync | PresentMode::Fifo | PresentMode::FifoRelaxed
+        )
+    }
+
+    /// Sets the present mode to [`PresentMode::Fifo`] if `vsync` is true,
+    /// otherwise [`PresentMode::Immediate`]
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.present_mode = if vsync {
+            PresentMode::Fifo
+        } else {
+            PresentMode::Immediate
+        };
+    }
+}
+
+/// How frames are presented to a window.
+///
+/// Mirrors [`wgpu::PresentMode`]. If the requested mode is not supported by the surface, the
+/// closest supported mode is used instead, ending with [`PresentMode::Fifo`] which is always supported.
+#[allow(dead_code)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PresentMode {
+    /// V-Sync if possible, chooses between [`FifoRelaxed`](PresentMode::FifoRelaxed) and [`Fifo`](PresentMode::Fifo)
+    #[default]
+    AutoVsync,
+    /// No V-Sync if possible, chooses between [`Immediate`](PresentMode::Immediate),
+    /// [`Mailbox`](PresentMode::Mailbox) and [`Fifo`](PresentMode::Fifo)
+    AutoNoVsync,
+    /// Frames are queued and presented on vertical blanks (traditional V-Sync)
+    Fifo,
+    /// Like [`Fifo`](PresentMode::Fifo), but a late frame is presented immediately and may tear
+    FifoRelaxed,
+    /// Frames are presented immediately and may tear
+    Immediate,
+    /// Only the latest frame is presented on vertical blanks, without tearing or blocking
+    Mailbox,
+}
+
+/// Identifies a monitor by its index in [`Monitors`](crate::client::core::window::resources::Monitors).
+///
+/// Indices can change when monitors are connected or disconnected.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct MonitorId(pub usize);
+
+/// Which texture format the surface of a window is configured with.
+///
+/// The format is validated against the formats the surface supports. If none match,
+/// the first sRGB format supported by the surface is used, falling back to the first
+/// supported format if the surface has no sRGB formats.
+#[allow(dead_code)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SurfaceFormat {
+    /// The first sRGB format supported by the surface
+    #[default]
+    Srgb,
+    /// The first non-sRGB format supported by the surface, for pipelines doing their own gamma correction
+    Linear,
+    /// A specific format (for example a 10-bit or floating point format for HDR), falls back to
+    /// [`Srgb`](SurfaceFormat::Srgb) if it is not supported
+    Exact(wgpu::TextureFormat),
+}
+
 /// A structure representing the resolution of the window
+///
+/// The logical size is stored and the physical size is derived from it, so changing the scale factor
+/// back and forth does not accumulate rounding errors.
 #[derive(Clone, PartialEq, Debug)]
 pub struct WindowResolution {
-    /// The physical width (pixels) of the window
-    physical_width: u32,
-    /// The physical height (pixels) of the window
-    physical_height: u32,
+    /// The logical width of the window
+    width: f64,
+    /// The logical height of the window
+    height: f64,
     /// The scale factor of the window
     scale_factor: f64,
 }
@@ -63,8 +148,8 @@ pub struct WindowResolution {
 impl Default for WindowResolution {
     fn default() -> Self {
         WindowResolution {
-            physical_width: 800,
-            physical_height: 600,
+            width: 800.0,
+            height: 600.0,
             scale_factor: 1.0,
         }
     }
@@ -75,32 +160,32 @@ impl WindowResolution {
     /// Creates a new window resolution with the given logical size
     pub fn new<P: Pixel>(logical_size: LogicalSize<P>) -> Self {
         // Assume the scale factor is 1 since it should be updated when the window is created
-        let physical_size = logical_size.to_physical(1.0);
+        let logical_size = logical_size.cast::<f64>();
         WindowResolution {
-            physical_width: physical_size.width,
-            physical_height: physical_size.height,
+            width: logical_size.width,
+            height: logical_size.height,
             scale_factor: 1.0,
         }
     }
 
     /// Creates a new window resolution with the given physical size and scale factor
     pub fn new_physical<P: Pixel>(physical_size: PhysicalSize<P>, scale_factor: f64) -> Self {
-        let physical_size = physical_size.cast::<u32>();
+        let logical_size = physical_size.to_logical::<f64>(scale_factor);
         WindowResolution {
-            physical_width: physical_size.width,
-            physical_height: physical_size.height,
+            width: logical_size.width,
+            height: logical_size.height,
             scale_factor,
         }
     }
 
     /// Returns the physical width of the window
     pub fn physical_width(&self) -> u32 {
-        self.physical_width
+        (self.width * self.scale_factor).round() as u32
     }
 
     /// Returns the physical height of the window
     pub fn physical_height(&self) -> u32 {
-        self.physical_height
+        (self.height * self.scale_factor).round() as u32
     }
 
     /// Returns the scale factor of the window
@@ -110,12 +195,12 @@ impl WindowResolution {
 
     /// Returns the logical width of the window
     pub fn width(&self) -> f64 {
-        self.physical_width as f64 / self.scale_factor
+        self.width
     }
 
     /// Returns the logical height of the window
     pub fn height(&self) -> f64 {
-        self.physical_height as f64 / self.scale_factor
+        self.height
     }
 
     /// Returns the logical size of the window
@@ -125,30 +210,27 @@ impl WindowResolution {
 
     /// Returns the physical size of the window
     pub fn physical_size(&self) -> PhysicalSize<u32> {
-        PhysicalSize::new(self.physical_width, self.physical_height)
+        PhysicalSize::new(self.physical_width(), self.physical_height())
     }
 
-    /// Sets the physical width of the window
+    /// Sets the physical size of the window
     pub fn set_physical_size<P: Pixel>(&mut self, physical_size: PhysicalSize<P>) {
-        let physical_size = physical_size.cast::<u32>();
-        self.physical_width = physical_size.width;
-        self.physical_height = physical_size.height;
+        let logical_size = physical_size.to_logical::<f64>(self.scale_factor);
+        self.width = logical_size.width;
+        self.height = logical_size.height;
     }
 
     /// Sets the logical size of the window
     pub fn set_logical_size<P: Pixel>(&mut self, logical_size: LogicalSize<P>) {
-        let physical_size: PhysicalSize<u32> = logical_size.to_physical(self.scale_factor);
-        self.set_physical_size(physical_size);
+        let logical_size = logical_size.cast::<f64>();
+        self.width = logical_size.width;
+        self.height = logical_size.height;
     }
 
     /// Sets the scale factor of the window
-    /// To ensure the logical size does not change, the physical size is adjusted based on the new scale factor
+    /// The logical size does not change, so the physical size follows the new scale factor
     pub fn set_scale_factor(&mut self, scale_factor: f64) {
-        let old_scale_factor = self.scale_factor;
         self.scale_factor = scale_factor;
-        self.physical_width = (self.physical_width as f64 / old_scale_factor * scale_factor) as u32;
-        self.physical_height =
-            (self.physical_height as f64 / old_scale_factor * scale_factor) as u32;
     }
 }
 
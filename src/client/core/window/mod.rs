@@ -14,7 +14,7 @@ use crate::client::core::window::components::{
 use crate::client::core::window::events::{
     CloseRequestedEvent, WindowCreatedEvent, WindowResizedEvent,
 };
-use crate::client::core::window::resources::WinitWindows;
+use crate::client::core::window::resources::{Monitors, Time, WinitWindows};
 use crate::client::core::window::systems::{
     l_react_to_resize, l_update_windows, pu_close_windows, pu_exit_on_all_closed,
     pu_exit_on_primary_closed, u_despawn_windows, u_primary_window_check,
@@ -25,6 +25,7 @@ use bevy_ecs::event::ManualEventReader;
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::SystemState;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::time::Instant;
 use tracing::{error, info, warn};
 use winit::application::ApplicationHandler;
 use winit::event::{StartCause, WindowEvent};
@@ -86,6 +87,8 @@ impl Plugin for WindowPlugin {
         });
         app.insert_non_send_resource(event_loop);
         app.insert_non_send_resource(WinitWindows::default());
+        app.init_resource::<Monitors>();
+        app.init_resource::<Time>();
 
         // Add systems
         app.add_systems(Update, u_primary_window_check);
@@ -210,8 +213,23 @@ impl ApplicationHandler for WinitApp {
                     new_height: size.to_logical(window.resolution.scale_factor()).height,
                 });
             }
-            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+            WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                mut inner_size_writer,
+            } => {
                 window.resolution.set_scale_factor(scale_factor);
+
+                // Keep the logical size so the window and its surface both follow the new scale
+                // factor. The extracted window sees the new physical size and reconfigures the surface.
+                let new_size = window.resolution.physical_size();
+                if let Err(err) = inner_size_writer.request_inner_size(new_size) {
+                    warn!("Failed to resize window {window_entity:?} after scale factor change: {err}");
+                }
+                window_resized_event.send(WindowResizedEvent {
+                    entity: window_entity,
+                    new_width: window.resolution.width(),
+                    new_height: window.resolution.height(),
+                });
                 //info!("Scale factor changed {}, {}, {}", window.resolution.physical_width(), window.resolution.physical_height(), window.resolution.scale_factor());
             }
             _ => {}
@@ -221,6 +239,10 @@ impl ApplicationHandler for WinitApp {
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         // Don't update if plugins are not ready
         if self.app.plugins_state() == PluginsState::Cleaned {
+            if let Some(mut time) = self.app.world.get_resource_mut::<Time>() {
+                time.update(Instant::now());
+            }
+
             // Run the frame
             self.app.update();
 
@@ -318,6 +340,13 @@ fn create_windows(
     mut window_created_event: EventWriter<WindowCreatedEvent>,
     event_loop: &ActiveEventLoop,
 ) {
+    if query.is_empty() {
+        return;
+    }
+
+    // Monitors might have changed since windows were last created
+    commands.insert_resource(winit_windows.update_monitors(event_loop));
+
     for (entity, mut window) in query.iter_mut() {
         // If the winit window already exists somehow, don't create another one
         if winit_windows.entity_to_window.contains_key(&entity) {
@@ -357,6 +386,13 @@ fn create_windows(
         window_created_event.send(WindowCreatedEvent {
             window_id: winit_window.id(),
         });
+
+        if let Some(monitor) = window.preferred_monitor {
+            if let Err(err) = winit_windows.move_to_monitor(entity, monitor) {
+                warn!("Failed to move window on {entity:?} to monitor: {err}");
+            }
+            commands.insert_resource(winit_windows.monitor_infos());
+        }
     }
 }
 
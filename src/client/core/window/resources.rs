@@ -1,13 +1,18 @@
 //! Bevy resources for the windowing module.
 
-use crate::client::core::window::components::Window;
-use bevy_ecs::prelude::Entity;
+use crate::client::core::window::components::{MonitorId, Window};
+use bevy_ecs::prelude::{Entity, Resource};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
+use std::ops::Deref;
+use std::time::{Duration, Instant};
 use tracing::{info, warn};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event_loop::ActiveEventLoop;
+use winit::monitor::MonitorHandle;
 use winit::window::{BadIcon, Icon, WindowId};
 
 /// Resource used to keep track of all the windows
@@ -20,6 +25,8 @@ pub struct WinitWindows {
     pub entity_to_window: HashMap<Entity, WindowId>,
     /// Maps from window ID to entity
     pub window_to_entity: HashMap<WindowId, Entity>,
+    /// The monitors available when they were last updated, indexed by [`MonitorId`]
+    pub monitors: Vec<MonitorHandle>,
     /// Marker to make this resource non-Send and Sync. This is because many winit functions cannot be called off the main thread.
     _not_send_sync: PhantomData<*const ()>,
 }
@@ -30,6 +37,7 @@ impl Default for WinitWindows {
             windows: HashMap::new(),
             entity_to_window: HashMap::new(),
             window_to_entity: HashMap::new(),
+            monitors: Vec::new(),
             _not_send_sync: PhantomData,
         }
     }
@@ -94,6 +102,189 @@ impl WinitWindows {
     pub fn get_window_entity(&self, window_id: WindowId) -> Option<Entity> {
         self.window_to_entity.get(&window_id).cloned()
     }
+
+    /// Queries the available monitors again.
+    ///
+    /// # Returns
+    /// A [`Monitors`] resource describing the monitors found.
+    pub fn update_monitors(&mut self, event_loop: &ActiveEventLoop) -> Monitors {
+        self.monitors = event_loop.available_monitors().collect();
+        self.monitor_infos()
+    }
+
+    /// Describes the monitors found when they were last queried.
+    ///
+    /// # Returns
+    /// A [`Monitors`] resource matching [`monitors`](WinitWindows::monitors).
+    pub fn monitor_infos(&self) -> Monitors {
+        Monitors(
+            self.monitors
+                .iter()
+                .enumerate()
+                .map(|(i, monitor)| MonitorInfo {
+                    id: MonitorId(i),
+                    name: monitor.name(),
+                    physical_size: monitor.size(),
+                    position: monitor.position(),
+                    refresh_rate_millihertz: monitor.refresh_rate_millihertz(),
+                    scale_factor: monitor.scale_factor(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Moves a window to the centre of a monitor.
+    ///
+    /// The monitors are queried again first, since they might have been connected or disconnected
+    /// since they were last updated. Update the [`Monitors`] resource with
+    /// [`monitor_infos`](WinitWindows::monitor_infos) afterwards.
+    pub fn move_to_monitor(
+        &mut self,
+        entity: Entity,
+        monitor: MonitorId,
+    ) -> Result<(), WindowError> {
+        let window_id = *self
+            .entity_to_window
+            .get(&entity)
+            .ok_or(WindowError::WindowEntity(entity))?;
+        let window = self
+            .windows
+            .get(&window_id)
+            .ok_or(WindowError::WindowEntity(entity))?;
+        self.monitors = window.available_monitors().collect();
+
+        let monitor_handle = self
+            .monitors
+            .get(monitor.0)
+            .ok_or(WindowError::Monitor(monitor))?;
+
+        let monitor_position = monitor_handle.position();
+        let monitor_size = monitor_handle.size();
+        let window_size = window.outer_size();
+
+        window.set_outer_position(PhysicalPosition::new(
+            monitor_position.x + (monitor_size.width as i32 - window_size.width as i32) / 2,
+            monitor_position.y + (monitor_size.height as i32 - window_size.height as i32) / 2,
+        ));
+
+        Ok(())
+    }
+}
+
+/// Resource listing the monitors available when they were last queried
+///
+/// Updated whenever windows are created or moved to a monitor.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Monitors(pub Vec<MonitorInfo>);
+
+impl Deref for Monitors {
+    type Target = Vec<MonitorInfo>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Information about a monitor, used to choose a [`MonitorId`] for [`Window::preferred_monitor`]
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct MonitorInfo {
+    /// The ID to refer to this monitor with
+    pub id: MonitorId,
+    /// The name of the monitor, if available
+    pub name: Option<String>,
+    /// The resolution of the monitor in pixels
+    pub physical_size: PhysicalSize<u32>,
+    /// The position of the top left corner of the monitor on the desktop
+    pub position: PhysicalPosition<i32>,
+    /// The refresh rate in millihertz, if available
+    pub refresh_rate_millihertz: Option<u32>,
+    /// The scale factor of the monitor
+    pub scale_factor: f64,
+}
+
+/// How much weight the latest frame has in the smoothed FPS of [`Time`]
+const FPS_SMOOTHING: f64 = 0.1;
+
+/// Resource containing the frame timing of the app
+///
+/// Updated by the event loop right before each frame runs, so every system in a frame sees the
+/// same values.
+#[derive(Resource, Debug)]
+pub struct Time {
+    /// When the app started
+    startup: Instant,
+    /// When the previous frame started
+    last_update: Option<Instant>,
+    /// Time between the start of the previous frame and this frame
+    delta: Duration,
+    /// Time since the app started
+    elapsed: Duration,
+    /// Exponentially smoothed frames per second
+    fps: f64,
+    /// Number of frames started so far
+    frame_count: u64,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Time {
+            startup: Instant::now(),
+            last_update: None,
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            fps: 0.0,
+            frame_count: 0,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Time {
+    /// Advances the time to a new frame starting at `now`
+    pub fn update(&mut self, now: Instant) {
+        if let Some(last_update) = self.last_update {
+            self.delta = now - last_update;
+        }
+        self.last_update = Some(now);
+        self.elapsed = now - self.startup;
+        self.frame_count += 1;
+
+        let delta_seconds = self.delta.as_secs_f64();
+        if delta_seconds > 0.0 {
+            let fps = 1.0 / delta_seconds;
+            self.fps = if self.fps == 0.0 {
+                fps
+            } else {
+                self.fps * (1.0 - FPS_SMOOTHING) + fps * FPS_SMOOTHING
+            };
+        }
+    }
+
+    /// Returns the time between the start of the previous frame and this frame
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// Returns [`delta`](Self::delta) in seconds
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// Returns the time since the app started
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the smoothed frames per second
+    pub fn fps(&self) -> f64 {
+        self.fps
+    }
+
+    /// Returns the number of frames started so far, including the current one
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
 }
 
 /// Handling various errors related to windowing
@@ -104,6 +295,8 @@ pub enum WindowError {
     Icon(BadIcon),
     /// Error on failure to create a window
     WindowCreation(winit::error::OsError),
+    /// Error when a monitor does not exist
+    Monitor(MonitorId),
 }
 
 impl Debug for WindowError {
@@ -122,6 +315,9 @@ impl Debug for WindowError {
             WindowError::WindowCreation(os_error) => {
                 write!(f, "Failed to create window: {:?}", os_error)
             }
+            WindowError::Monitor(monitor) => {
+                write!(f, "Monitor {:?} does not exist", monitor)
+            }
         }
     }
 }
@@ -142,6 +338,9 @@ impl Display for WindowError {
             WindowError::WindowCreation(os_error) => {
                 write!(f, "Failed to create window: {os_error}")
             }
+            WindowError::Monitor(monitor) => {
+                write!(f, "Monitor {} does not exist", monitor.0)
+            }
         }
     }
 }
@@ -152,6 +351,7 @@ impl Error for WindowError {
             WindowError::WindowEntity(_) => None,
             WindowError::Icon(bad_icon) => Some(bad_icon),
             WindowError::WindowCreation(os_error) => Some(os_error),
+            WindowError::Monitor(_) => None,
         }
     }
 }
@@ -2,7 +2,7 @@
 
 use crate::client::core::window::components::{CachedWindow, PrimaryWindow, Window};
 use crate::client::core::window::events::{CloseRequestedEvent, WindowResizedEvent};
-use crate::client::core::window::resources::WinitWindows;
+use crate::client::core::window::resources::{Monitors, WinitWindows};
 use bevy_app::AppExit;
 use bevy_ecs::prelude::*;
 use tracing::{info, warn};
@@ -13,7 +13,8 @@ use winit::dpi::LogicalSize;
 /// Called on `Late` to update the winit window when the window component changes
 pub fn l_update_windows(
     mut query: Query<(Entity, &mut Window, &mut CachedWindow), Changed<Window>>,
-    winit_windows: NonSendMut<WinitWindows>,
+    mut winit_windows: NonSendMut<WinitWindows>,
+    mut monitors: ResMut<Monitors>,
     mut window_resized: EventWriter<WindowResizedEvent>,
 ) {
     for (entity, mut window, mut cache) in query.iter_mut() {
@@ -53,6 +54,15 @@ pub fn l_update_windows(
             }
         }
 
+        if window.preferred_monitor != cache.0.preferred_monitor {
+            if let Some(monitor) = window.preferred_monitor {
+                if let Err(err) = winit_windows.move_to_monitor(entity, monitor) {
+                    warn!("Failed to move window on {entity:?} to monitor: {err}");
+                }
+                *monitors = winit_windows.monitor_infos();
+            }
+        }
+
         cache.0 = window.clone();
     }
 }
@@ -5,8 +5,10 @@ mod camera;
 pub mod core;
 
 use crate::client::camera::systems::s_spawn_camera;
+use crate::client::core::graphics::camera::resources::ClearColor;
 use crate::client::core::graphics::camera::CameraPlugin;
 use bevy_app::{App, Plugin, Startup};
+use wgpu::Color;
 
 /// Plugin that contains everything the game uses.
 pub struct GamePlugin;
@@ -17,6 +19,14 @@ impl Plugin for GamePlugin {
             app.add_plugins(CameraPlugin);
         }
 
+        // Windows blue
+        app.insert_resource(ClearColor(Color {
+            r: 0.0,
+            g: 0.6328125,
+            b: 0.92578125,
+            a: 1.0,
+        }));
+
         app.add_systems(Startup, s_spawn_camera);
     }
 }
@@ -19,7 +19,7 @@ impl PluginGroup for ClientPlugins {
             .add(CorePlugin)
             .add(GraphicsPlugin)
             .add_after::<GraphicsPlugin, _>(GamePlugin)
-            .add_after::<GraphicsPlugin, _>(PipelinedRenderingPlugin);
+            .add_after::<GraphicsPlugin, _>(PipelinedRenderingPlugin::default());
 
         group
     }